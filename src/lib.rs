@@ -1,9 +1,13 @@
 pub mod raw;
 
+mod mps;
+
 pub use raw::Sense;
 
-use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io;
 use std::os::raw::c_int;
+use std::path::Path;
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Col(u32);
@@ -21,6 +25,48 @@ impl Row {
     }
 }
 
+/// Sparse `(Col, Row) -> weight` coefficient storage. Each column keeps its
+/// `(Row, weight)` entries directly in a flat `Vec`, so reading a column back
+/// out (as `to_raw`/`write_mps`/`write_lp` all do) is a clone-and-sort with no
+/// further lookups; a hash-backed index into those vectors gives `set` the
+/// same O(1) amortized insert/update/remove a `BTreeMap` never had. Setting a
+/// weight to exactly `0.` deletes the entry, same as before.
+#[derive(Default, Clone)]
+struct Weights {
+    columns: Vec<Vec<(Row, f64)>>,
+    index: HashMap<(Col, Row), usize>,
+}
+
+impl Weights {
+    fn add_col(&mut self) {
+        self.columns.push(Vec::new());
+    }
+    fn set(&mut self, col: Col, row: Row, weight: f64) {
+        if weight == 0. {
+            if let Some(pos) = self.index.remove(&(col, row)) {
+                let entries = &mut self.columns[col.as_usize()];
+                entries.swap_remove(pos);
+                // `swap_remove` moved the last entry into `pos`; repoint its index.
+                if let Some(&(moved_row, _)) = entries.get(pos) {
+                    self.index.insert((col, moved_row), pos);
+                }
+            }
+        } else if let Some(&pos) = self.index.get(&(col, row)) {
+            self.columns[col.as_usize()][pos].1 = weight;
+        } else {
+            let pos = self.columns[col.as_usize()].len();
+            self.columns[col.as_usize()].push((row, weight));
+            self.index.insert((col, row), pos);
+        }
+    }
+    /// This column's `(Row, weight)` entries, sorted by row.
+    fn col_entries(&self, col: Col) -> Vec<(Row, f64)> {
+        let mut entries = self.columns[col.as_usize()].clone();
+        entries.sort_unstable_by_key(|&(row, _)| row);
+        entries
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct Model {
     num_cols: u32,
@@ -30,9 +76,50 @@ pub struct Model {
     row_lower: Vec<f64>,
     row_upper: Vec<f64>,
     obj_coefficients: Vec<f64>,
-    weights: Vec<BTreeMap<Row, f64>>,
+    weights: Weights,
     is_integer: Vec<bool>,
     sense: Sense,
+    initial_solution: Vec<(Col, f64)>,
+}
+
+/// Resource limits and verbosity forwarded to CBC for a single solve, so a
+/// hard MILP cannot consume unbounded CPU and memory. All fields are
+/// optional; an unset field leaves CBC's own default in place.
+#[derive(Default, Clone)]
+pub struct SolveOptions {
+    time_limit_seconds: Option<f64>,
+    max_nodes: Option<c_int>,
+    absolute_gap: Option<f64>,
+    relative_gap: Option<f64>,
+    threads: Option<c_int>,
+    log_level: Option<c_int>,
+}
+
+impl SolveOptions {
+    /// Stops the search once `seconds` of wall-clock time have elapsed.
+    pub fn set_time_limit_seconds(&mut self, seconds: f64) {
+        self.time_limit_seconds = Some(seconds);
+    }
+    /// Stops the search once `nodes` branch-and-bound nodes have been explored.
+    pub fn set_max_nodes(&mut self, nodes: c_int) {
+        self.max_nodes = Some(nodes);
+    }
+    /// Stops the search once the absolute gap to the best bound is at most `gap`.
+    pub fn set_absolute_gap(&mut self, gap: f64) {
+        self.absolute_gap = Some(gap);
+    }
+    /// Stops the search once the relative gap to the best bound is at most `gap`.
+    pub fn set_relative_gap(&mut self, gap: f64) {
+        self.relative_gap = Some(gap);
+    }
+    /// Number of threads CBC may use for the search.
+    pub fn set_threads(&mut self, threads: c_int) {
+        self.threads = Some(threads);
+    }
+    /// CBC log verbosity, from 0 (silent) up.
+    pub fn set_log_level(&mut self, level: c_int) {
+        self.log_level = Some(level);
+    }
 }
 
 impl Model {
@@ -40,7 +127,7 @@ impl Model {
         let col = Col(self.num_cols);
         self.num_cols += 1;
         self.obj_coefficients.push(0.);
-        self.weights.push(Default::default());
+        self.weights.add_col();
         self.is_integer.push(false);
         self.col_lower.push(0.);
         self.col_upper.push(f64::INFINITY);
@@ -54,12 +141,21 @@ impl Model {
         row
     }
     pub fn set_weight(&mut self, row: Row, col: Col, weight: f64) {
-        if weight == 0. {
-            self.weights[col.as_usize()].remove(&row);
-        } else {
-            self.weights[col.as_usize()].insert(row, weight);
+        self.weights.set(col, row, weight);
+    }
+    /// Sets several weights in `col` at once.
+    pub fn set_weights(&mut self, col: Col, weights: &[(Row, f64)]) {
+        for &(row, weight) in weights {
+            self.weights.set(col, row, weight);
         }
     }
+    /// Sets `col`'s bounds, objective coefficient, and weights in one call.
+    pub fn set_column(&mut self, col: Col, lower: f64, upper: f64, obj: f64, weights: &[(Row, f64)]) {
+        self.set_col_lower(col, lower);
+        self.set_col_upper(col, upper);
+        self.set_obj_coeff(col, obj);
+        self.set_weights(col, weights);
+    }
     pub fn set_integer(&mut self, col: Col) {
         self.is_integer[col.as_usize()] = true;
     }
@@ -89,21 +185,42 @@ impl Model {
     pub fn set_obj_sense(&mut self, sense: Sense) {
         self.sense = sense;
     }
-    pub fn to_raw(&self) -> raw::Model {
+    /// Supplies an initial feasible (or partial) solution CBC can start
+    /// branch-and-bound from. Columns not present are left for CBC to decide.
+    /// A no-op when `assignment` is empty.
+    pub fn set_initial_solution(&mut self, assignment: &[(Col, f64)]) {
+        if assignment.is_empty() {
+            return;
+        }
+        for &(col, _) in assignment {
+            assert!(
+                col.as_usize() < self.num_cols as usize,
+                "column {} is out of range for a model with {} columns",
+                col.as_usize(),
+                self.num_cols
+            );
+        }
+        self.initial_solution = assignment.to_vec();
+    }
+    /// Builds the sparse column-major (CSC) representation of the constraint
+    /// matrix: `start[col]..start[col + 1]` indexes into `index`/`value` for
+    /// the nonzero entries of that column, sorted by `Row`.
+    fn build_csc(&self) -> (Vec<c_int>, Vec<c_int>, Vec<f64>) {
         let mut start = Vec::with_capacity(self.num_cols as usize + 1);
         let mut index = Vec::with_capacity(self.num_cols.max(self.num_rows) as usize);
         let mut value = Vec::with_capacity(self.num_cols.max(self.num_rows) as usize);
         start.push(0);
-        for col_weights in &self.weights {
-            for (r, w) in col_weights {
-                index.push(r.0 as c_int);
-                value.push(*w);
+        for col in 0..self.num_cols {
+            for (row, weight) in self.weights.col_entries(Col(col)) {
+                index.push(row.0 as c_int);
+                value.push(weight);
             }
             start.push(index.len() as c_int);
         }
-        dbg!(&start);
-        dbg!(&index);
-        dbg!(&value);
+        (start, index, value)
+    }
+    pub fn to_raw(&self) -> raw::Model {
+        let (start, index, value) = self.build_csc();
         let mut raw = raw::Model::new();
         raw.load_problem(
             self.num_cols as usize,
@@ -125,13 +242,68 @@ impl Model {
             }
         }
         raw.set_obj_sense(self.sense);
+        if !self.initial_solution.is_empty() {
+            let columns: Vec<c_int> = self
+                .initial_solution
+                .iter()
+                .map(|&(col, _)| col.as_usize() as c_int)
+                .collect();
+            let values: Vec<f64> = self.initial_solution.iter().map(|&(_, value)| value).collect();
+            raw.set_mip_start(&columns, &values);
+        }
         raw
     }
     pub fn solve(&self) -> Solution {
+        self.solve_with(&SolveOptions::default())
+    }
+
+    /// Solves the model, forwarding `options` to CBC's time/node/gap limits,
+    /// thread count, and log verbosity before branch-and-bound starts.
+    pub fn solve_with(&self, options: &SolveOptions) -> Solution {
         let mut raw = self.to_raw();
+        if let Some(seconds) = options.time_limit_seconds {
+            raw.set_maximum_seconds(seconds);
+        }
+        if let Some(nodes) = options.max_nodes {
+            raw.set_maximum_node_count(nodes);
+        }
+        if let Some(gap) = options.absolute_gap {
+            raw.set_allowable_gap(gap);
+        }
+        if let Some(gap) = options.relative_gap {
+            raw.set_allowable_fraction_gap(gap);
+        }
+        if let Some(threads) = options.threads {
+            raw.set_threads(threads);
+        }
+        if let Some(level) = options.log_level {
+            raw.set_log_level(level);
+        }
         raw.solve();
         Solution { raw }
     }
+
+    /// Writes this model to `path` in free MPS format (whitespace-separated
+    /// fields, not fixed column positions). Row and column names
+    /// are synthesized as `R0`, `R1`, ... and `C0`, `C1`, ... . A row with
+    /// finite, distinct lower and upper bounds is written as a ranged row
+    /// (`RANGES` section).
+    pub fn write_mps(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        mps::write_mps(self, path.as_ref())
+    }
+
+    /// Writes this model to `path` in CPLEX LP format. See [`Model::write_mps`]
+    /// for the row/column naming convention.
+    pub fn write_lp(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        mps::write_lp(self, path.as_ref())
+    }
+
+    /// Reads an MPS file at `path` via CBC's MPS reader and reconstructs a
+    /// `Model`, mapping the imported columns and rows back into `Col`/`Row`
+    /// index space.
+    pub fn read_mps(path: impl AsRef<Path>) -> io::Result<Model> {
+        mps::read_mps(path.as_ref())
+    }
 }
 
 pub struct Solution {
@@ -147,6 +319,83 @@ impl Solution {
     pub fn col(&self, col: Col) -> f64 {
         self.raw.col_solution()[col.as_usize()]
     }
+    /// The dual price (shadow price) of `row`.
+    pub fn dual(&self, row: Row) -> f64 {
+        self.raw.row_price()[row.as_usize()]
+    }
+    /// The reduced cost of `col`.
+    pub fn reduced_cost(&self, col: Col) -> f64 {
+        self.raw.reduced_cost()[col.as_usize()]
+    }
+    /// The value of `row`'s left-hand side at the solution.
+    pub fn row_activity(&self, row: Row) -> f64 {
+        self.raw.row_activity()[row.as_usize()]
+    }
+    /// The objective value of the solution, without reaching through `raw()`.
+    pub fn objective_value(&self) -> f64 {
+        self.raw.obj_value()
+    }
+    /// The typed status of the solve, combining CBC's primary and secondary
+    /// status codes into something a caller can match on directly instead of
+    /// guessing what `raw::Status::Finished` means for an infeasible model.
+    pub fn status(&self) -> SolutionStatus {
+        SolutionStatus::from_raw(self.raw.status(), self.raw.secondary_status())
+    }
+    /// Whether the solve proved the returned solution optimal.
+    pub fn is_proven_optimal(&self) -> bool {
+        self.status() == SolutionStatus::Optimal
+    }
+    /// Whether the solve proved the model infeasible.
+    pub fn is_proven_infeasible(&self) -> bool {
+        self.status() == SolutionStatus::Infeasible
+    }
+}
+
+// CBC's secondary status codes (`CbcModel::secondaryStatus()`), as documented
+// by CbcModel: 0 search completed with a solution, 1 the (relaxation is)
+// infeasible, 2-6 and 8 the search was stopped before a proof (on gap, node,
+// time, user event, solution-count, or iteration limit respectively), 7 the
+// linear relaxation is unbounded.
+const CBC_SECONDARY_OPTIMAL: i32 = 0;
+const CBC_SECONDARY_INFEASIBLE: i32 = 1;
+const CBC_SECONDARY_STOPPED_ON_GAP: i32 = 2;
+const CBC_SECONDARY_STOPPED_ON_NODES: i32 = 3;
+const CBC_SECONDARY_STOPPED_ON_TIME: i32 = 4;
+const CBC_SECONDARY_STOPPED_ON_USER_EVENT: i32 = 5;
+const CBC_SECONDARY_STOPPED_ON_SOLUTION_LIMIT: i32 = 6;
+const CBC_SECONDARY_UNBOUNDED: i32 = 7;
+const CBC_SECONDARY_STOPPED_ON_ITERATION_LIMIT: i32 = 8;
+
+/// A typed read of CBC's status and secondary-status codes, since
+/// `raw::Status::Finished` alone does not say whether the model was solved to
+/// optimality, found infeasible, or stopped early on a limit.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SolutionStatus {
+    Optimal,
+    Infeasible,
+    Unbounded,
+    /// Stopped before a proof, e.g. on a time/node/gap limit from `SolveOptions`.
+    Stopped,
+    /// Search never reached a conclusive primary or secondary status.
+    Unknown,
+}
+
+impl SolutionStatus {
+    fn from_raw(status: raw::Status, secondary_status: i32) -> Self {
+        match secondary_status {
+            CBC_SECONDARY_OPTIMAL => SolutionStatus::Optimal,
+            CBC_SECONDARY_INFEASIBLE => SolutionStatus::Infeasible,
+            CBC_SECONDARY_UNBOUNDED => SolutionStatus::Unbounded,
+            CBC_SECONDARY_STOPPED_ON_GAP
+            | CBC_SECONDARY_STOPPED_ON_NODES
+            | CBC_SECONDARY_STOPPED_ON_TIME
+            | CBC_SECONDARY_STOPPED_ON_USER_EVENT
+            | CBC_SECONDARY_STOPPED_ON_SOLUTION_LIMIT
+            | CBC_SECONDARY_STOPPED_ON_ITERATION_LIMIT => SolutionStatus::Stopped,
+            _ if status == raw::Status::Stopped => SolutionStatus::Stopped,
+            _ => SolutionStatus::Unknown,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -189,4 +438,170 @@ mod test {
         assert_eq!(1., sol.col(cols[3]));
         assert_eq!(1., sol.col(cols[4]));
     }
+
+    #[test]
+    fn solve_with_max_nodes_zero_stops_early() {
+        let mut m = Model::default();
+        let row = m.add_row();
+        m.set_row_upper(row, 10.);
+        let cols = vec![
+            m.add_col(),
+            m.add_col(),
+            m.add_col(),
+            m.add_col(),
+            m.add_col(),
+        ];
+        for &c in &cols {
+            m.set_binary(c);
+        }
+        m.set_weight(row, cols[0], 2.);
+        m.set_weight(row, cols[1], 8.);
+        m.set_weight(row, cols[2], 4.);
+        m.set_weight(row, cols[3], 2.);
+        m.set_weight(row, cols[4], 5.);
+        m.set_obj_coeff(cols[0], 5.);
+        m.set_obj_coeff(cols[1], 3.);
+        m.set_obj_coeff(cols[2], 2.);
+        m.set_obj_coeff(cols[3], 7.);
+        m.set_obj_coeff(cols[4], 4.);
+        m.set_obj_sense(Sense::Maximize);
+
+        let mut options = SolveOptions::default();
+        options.set_max_nodes(0);
+        let sol = m.solve_with(&options);
+        assert_eq!(SolutionStatus::Stopped, sol.status());
+    }
+
+    #[test]
+    fn mps_round_trip() {
+        let mut m = Model::default();
+        // Left at the default [-inf, inf]: a free, report-only row.
+        let free_row = m.add_row();
+        let capacity_row = m.add_row();
+        m.set_row_upper(capacity_row, 10.);
+
+        let bin = m.add_col();
+        m.set_binary(bin);
+        let unbounded_int = m.add_col();
+        // Left at the continuous default [0, inf): a general integer column.
+        m.set_integer(unbounded_int);
+
+        m.set_weight(free_row, bin, 1.);
+        m.set_weight(capacity_row, bin, 2.);
+        m.set_weight(capacity_row, unbounded_int, 3.);
+        m.set_obj_coeff(bin, 5.);
+        m.set_obj_coeff(unbounded_int, 7.);
+        m.set_obj_sense(Sense::Maximize);
+
+        let path =
+            std::env::temp_dir().join(format!("coin_cbc_round_trip_{}.mps", std::process::id()));
+        m.write_mps(&path).unwrap();
+        let round_tripped = Model::read_mps(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(2, round_tripped.num_cols);
+        assert_eq!(2, round_tripped.num_rows);
+        assert!(round_tripped.is_integer[unbounded_int.as_usize()]);
+        assert_eq!(0., round_tripped.col_lower[unbounded_int.as_usize()]);
+        assert_eq!(
+            f64::INFINITY,
+            round_tripped.col_upper[unbounded_int.as_usize()]
+        );
+        assert_eq!(
+            f64::NEG_INFINITY,
+            round_tripped.row_lower[free_row.as_usize()]
+        );
+        assert_eq!(f64::INFINITY, round_tripped.row_upper[free_row.as_usize()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn set_initial_solution_rejects_out_of_range_col() {
+        let mut m = Model::default();
+        m.add_col();
+        m.set_initial_solution(&[(Col(1), 1.)]);
+    }
+
+    #[test]
+    fn set_initial_solution_round_trips_through_solve() {
+        let mut m = Model::default();
+        let row = m.add_row();
+        m.set_row_upper(row, 10.);
+        let cols = vec![
+            m.add_col(),
+            m.add_col(),
+            m.add_col(),
+            m.add_col(),
+            m.add_col(),
+        ];
+        for &c in &cols {
+            m.set_binary(c);
+        }
+        m.set_weight(row, cols[0], 2.);
+        m.set_weight(row, cols[1], 8.);
+        m.set_weight(row, cols[2], 4.);
+        m.set_weight(row, cols[3], 2.);
+        m.set_weight(row, cols[4], 5.);
+        m.set_obj_coeff(cols[0], 5.);
+        m.set_obj_coeff(cols[1], 3.);
+        m.set_obj_coeff(cols[2], 2.);
+        m.set_obj_coeff(cols[3], 7.);
+        m.set_obj_coeff(cols[4], 4.);
+        m.set_obj_sense(Sense::Maximize);
+
+        // A partial assignment: CBC is left to decide the rest.
+        m.set_initial_solution(&[(cols[0], 1.), (cols[3], 1.)]);
+
+        let sol = m.solve();
+        assert_eq!(raw::Status::Finished, sol.raw().status());
+        assert_eq!(16., sol.raw().obj_value());
+    }
+
+    #[test]
+    fn write_lp_smoke() {
+        let mut m = Model::default();
+        let row = m.add_row();
+        m.set_row_upper(row, 10.);
+        let col = m.add_col();
+        m.set_integer(col);
+        m.set_weight(row, col, 2.);
+        m.set_obj_coeff(col, 5.);
+        m.set_obj_sense(Sense::Maximize);
+
+        let path = std::env::temp_dir().join(format!("coin_cbc_smoke_{}.lp", std::process::id()));
+        m.write_lp(&path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(contents.contains("Maximize"));
+        assert!(contents.contains("General"));
+        assert!(contents.contains("R0"));
+    }
+
+    #[test]
+    fn solution_status_from_raw() {
+        assert_eq!(
+            SolutionStatus::Optimal,
+            SolutionStatus::from_raw(raw::Status::Finished, 0)
+        );
+        assert_eq!(
+            SolutionStatus::Infeasible,
+            SolutionStatus::from_raw(raw::Status::Finished, 1)
+        );
+        assert_eq!(
+            SolutionStatus::Unbounded,
+            SolutionStatus::from_raw(raw::Status::Finished, 7)
+        );
+        // Stopped on a time limit with a feasible solution in hand still
+        // reports `Finished` as the primary status; the secondary status is
+        // what distinguishes it from a proven-optimal solve.
+        assert_eq!(
+            SolutionStatus::Stopped,
+            SolutionStatus::from_raw(raw::Status::Finished, 4)
+        );
+        assert_eq!(
+            SolutionStatus::Stopped,
+            SolutionStatus::from_raw(raw::Status::Stopped, 3)
+        );
+    }
 }
\ No newline at end of file
@@ -0,0 +1,275 @@
+//! Free MPS and CPLEX-LP serialization for [`Model`], plus MPS import.
+//!
+//! Row and column names are synthesized from their index (`R0`, `R1`, ... and
+//! `C0`, `C1`, ...) since `Model` does not currently track user-facing names.
+
+use crate::{Col, Model, Row};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+// CBC's own convention for "infinite" in a grammar that has no literal
+// infinity token: a large finite number. `f64::INFINITY`/`NEG_INFINITY`
+// formatted via `{}` render as the non-numeric text `inf`/`-inf`, which is
+// not a valid MPS or LP token (and a lenient parser may read it as `0`).
+const MPS_INFINITY: f64 = 1e30;
+
+fn finite_or_sentinel(v: f64) -> f64 {
+    if v == f64::INFINITY {
+        MPS_INFINITY
+    } else if v == f64::NEG_INFINITY {
+        -MPS_INFINITY
+    } else {
+        v
+    }
+}
+
+fn row_name(row: usize) -> String {
+    format!("R{}", row)
+}
+fn col_name(col: usize) -> String {
+    format!("C{}", col)
+}
+
+// A row is stored as `[row_lower, row_upper]`. MPS only has one RHS plus an
+// optional RANGES value, so pick the "primary" bound the way CBC itself does:
+// prefer `L` (<=) when there is a finite upper bound, otherwise `G` (>=), and
+// `E` when both bounds coincide. A row with two finite, distinct bounds is a
+// ranged `L` row with `RANGES = upper - lower`.
+enum RowKind {
+    Less,
+    Greater,
+    Equal,
+}
+
+fn row_kind(lower: f64, upper: f64) -> (RowKind, f64, Option<f64>) {
+    if lower == upper {
+        (RowKind::Equal, finite_or_sentinel(lower), None)
+    } else if upper.is_finite() {
+        let range = if lower.is_finite() {
+            Some(upper - lower)
+        } else {
+            None
+        };
+        (RowKind::Less, upper, range)
+    } else if lower.is_finite() {
+        (RowKind::Greater, lower, None)
+    } else {
+        // Free row: CBC treats an all-infinite row as non-binding; emit it as
+        // `G -1e30` so the grammar stays valid and round-trips to no-op bounds.
+        (RowKind::Greater, -MPS_INFINITY, None)
+    }
+}
+
+pub(crate) fn write_mps(model: &Model, path: &Path) -> io::Result<()> {
+    let (start, index, value) = model.build_csc();
+    let mut out = BufWriter::new(File::create(path)?);
+
+    writeln!(out, "NAME")?;
+    writeln!(out, "ROWS")?;
+    writeln!(out, " N  COST")?;
+    for row in 0..model.num_rows as usize {
+        let (kind, ..) = row_kind(model.row_lower[row], model.row_upper[row]);
+        let letter = match kind {
+            RowKind::Less => "L",
+            RowKind::Greater => "G",
+            RowKind::Equal => "E",
+        };
+        writeln!(out, " {}  {}", letter, row_name(row))?;
+    }
+
+    writeln!(out, "COLUMNS")?;
+    for col in 0..model.num_cols as usize {
+        if model.is_integer[col] {
+            writeln!(out, "    MARKER                 'MARKER'                 'INTORG'")?;
+        }
+        let obj = model.obj_coefficients[col];
+        let mut wrote_entry = false;
+        if obj != 0. {
+            writeln!(out, "    {}  COST  {}", col_name(col), obj)?;
+            wrote_entry = true;
+        }
+        for i in start[col] as usize..start[col + 1] as usize {
+            writeln!(
+                out,
+                "    {}  {}  {}",
+                col_name(col),
+                row_name(index[i] as usize),
+                value[i]
+            )?;
+            wrote_entry = true;
+        }
+        if !wrote_entry {
+            // A column exists in MPS only by virtue of appearing in COLUMNS;
+            // an all-zero column still needs a line so it isn't dropped and
+            // every later column's index doesn't shift on `read_mps`.
+            writeln!(out, "    {}  COST  0", col_name(col))?;
+        }
+        if model.is_integer[col] {
+            writeln!(out, "    MARKER                 'MARKER'                 'INTEND'")?;
+        }
+    }
+
+    writeln!(out, "RHS")?;
+    for row in 0..model.num_rows as usize {
+        let (_, rhs, _) = row_kind(model.row_lower[row], model.row_upper[row]);
+        if rhs != 0. {
+            writeln!(out, "    RHS  {}  {}", row_name(row), rhs)?;
+        }
+    }
+
+    let ranges: Vec<(usize, f64)> = (0..model.num_rows as usize)
+        .filter_map(|row| {
+            let (_, _, range) = row_kind(model.row_lower[row], model.row_upper[row]);
+            range.map(|r| (row, r))
+        })
+        .collect();
+    if !ranges.is_empty() {
+        writeln!(out, "RANGES")?;
+        for (row, range) in ranges {
+            writeln!(out, "    RNG  {}  {}", row_name(row), range)?;
+        }
+    }
+
+    writeln!(out, "BOUNDS")?;
+    for col in 0..model.num_cols as usize {
+        let lower = model.col_lower[col];
+        let upper = model.col_upper[col];
+        let is_integer = model.is_integer[col];
+
+        if lower == 0. && upper == f64::INFINITY && !is_integer {
+            continue;
+        }
+        if lower == f64::NEG_INFINITY && upper == f64::INFINITY {
+            writeln!(out, " FR BND  {}", col_name(col))?;
+            continue;
+        }
+        if lower == upper {
+            writeln!(out, " FX BND  {}  {}", col_name(col), finite_or_sentinel(lower))?;
+            continue;
+        }
+        if lower == f64::NEG_INFINITY {
+            writeln!(out, " MI BND  {}", col_name(col))?;
+        } else if lower != 0. {
+            writeln!(out, " LO BND  {}  {}", col_name(col), lower)?;
+        }
+        if upper.is_finite() {
+            writeln!(out, " UP BND  {}  {}", col_name(col), upper)?;
+        } else if is_integer {
+            // Classic MPS semantics (CoinMpsIO) default an integer column
+            // inside INTORG/INTEND with no BOUNDS entry to [0, 1], not
+            // [0, inf) like a continuous column. Make the infinite upper
+            // bound explicit so this doesn't silently round-trip as binary.
+            writeln!(out, " UP BND  {}  {}", col_name(col), MPS_INFINITY)?;
+        }
+    }
+
+    writeln!(out, "ENDATA")?;
+    out.flush()
+}
+
+pub(crate) fn write_lp(model: &Model, path: &Path) -> io::Result<()> {
+    let (start, index, value) = model.build_csc();
+    let mut out = BufWriter::new(File::create(path)?);
+
+    writeln!(
+        out,
+        "{}",
+        match model.sense {
+            crate::Sense::Maximize => "Maximize",
+            _ => "Minimize",
+        }
+    )?;
+    let obj_terms: Vec<String> = (0..model.num_cols as usize)
+        .filter(|&c| model.obj_coefficients[c] != 0.)
+        .map(|c| format!("{} {}", model.obj_coefficients[c], col_name(c)))
+        .collect();
+    writeln!(out, " obj: {}", obj_terms.join(" + "))?;
+
+    writeln!(out, "Subject To")?;
+    for row in 0..model.num_rows as usize {
+        let (kind, rhs, _) = row_kind(model.row_lower[row], model.row_upper[row]);
+        let op = match kind {
+            RowKind::Less => "<=",
+            RowKind::Greater => ">=",
+            RowKind::Equal => "=",
+        };
+        let mut terms = Vec::new();
+        for col in 0..model.num_cols as usize {
+            for i in start[col] as usize..start[col + 1] as usize {
+                if index[i] as usize == row {
+                    terms.push(format!("{} {}", value[i], col_name(col)));
+                }
+            }
+        }
+        writeln!(out, " {}: {} {} {}", row_name(row), terms.join(" + "), op, rhs)?;
+    }
+
+    writeln!(out, "Bounds")?;
+    for col in 0..model.num_cols as usize {
+        let lower = model.col_lower[col];
+        let upper = model.col_upper[col];
+        // Same classic-format gotcha as `write_mps`: CPLEX LP defaults a
+        // `General` integer variable with no explicit bound to [0, 1], so an
+        // unbounded integer column still needs a line here.
+        if lower == 0. && upper == f64::INFINITY && !model.is_integer[col] {
+            continue;
+        }
+        writeln!(
+            out,
+            " {} <= {} <= {}",
+            finite_or_sentinel(lower),
+            col_name(col),
+            finite_or_sentinel(upper)
+        )?;
+    }
+
+    let integers: Vec<usize> = (0..model.num_cols as usize)
+        .filter(|&c| model.is_integer[c])
+        .collect();
+    if !integers.is_empty() {
+        writeln!(out, "General")?;
+        for col in integers {
+            writeln!(out, " {}", col_name(col))?;
+        }
+    }
+
+    writeln!(out, "End")?;
+    out.flush()
+}
+
+pub(crate) fn read_mps(path: &Path) -> io::Result<Model> {
+    let raw = crate::raw::Model::read_mps(path)?;
+
+    let num_cols = raw.num_cols();
+    let num_rows = raw.num_rows();
+    let mut model = Model {
+        num_cols: num_cols as u32,
+        num_rows: num_rows as u32,
+        col_lower: raw.col_lower().to_vec(),
+        col_upper: raw.col_upper().to_vec(),
+        row_lower: raw.row_lower().to_vec(),
+        row_upper: raw.row_upper().to_vec(),
+        obj_coefficients: raw.obj_coefficients().to_vec(),
+        weights: Default::default(),
+        is_integer: (0..num_cols).map(|c| raw.is_integer(c)).collect(),
+        sense: raw.sense(),
+        initial_solution: Vec::new(),
+    };
+    for _ in 0..num_cols {
+        model.weights.add_col();
+    }
+
+    let start = raw.matrix_col_start();
+    let index = raw.matrix_row_index();
+    let value = raw.matrix_value();
+    for col in 0..num_cols {
+        for i in start[col] as usize..start[col + 1] as usize {
+            model
+                .weights
+                .set(Col(col as u32), Row(index[i] as u32), value[i]);
+        }
+    }
+
+    Ok(model)
+}